@@ -0,0 +1,16 @@
+mod tableland;
+
+pub use tableland::{call as call_tableland, PrecompileContext, PrecompileError, TABLELAND_PRECOMPILE_ADDR};
+
+// NOTE: this snapshot only carries the tableland bridge itself -- the rest
+// of this crate (the interpreter, its opcode dispatch, and its existing
+// `src/lib.rs` with the 0x01-0x09 precompile table) isn't part of this tree.
+// Wiring this module in is two edits against that unseen source: add
+// `pub mod precompiles;` to `src/lib.rs`, and add a
+// `TABLELAND_PRECOMPILE_ADDR => precompiles::call_tableland(rt, input, ctx)`
+// arm next to the existing built-in addresses in the interpreter's precompile
+// dispatch, passing through the frame's `is_static` flag as `ctx` the same
+// way it already gates `SSTORE` under `STATICCALL`. `call_tableland` resolves
+// the tableland actor's own address internally (it's a fixed `ActorID`, not
+// derived from the call's `target`, which under this dispatch arm is still
+// the precompile's own EVM address `0xfe`).
@@ -0,0 +1,205 @@
+use fil_actor_tableland_interface::{
+    ExecuteParams, ExecuteReturn, Method as TablelandMethod, QueryParams, QueryReturn,
+    TABLELAND_ACTOR_ID,
+};
+use fil_actors_runtime::runtime::Runtime;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_shared::address::Address;
+use fvm_shared::econ::TokenAmount;
+use rusqlite::types::Value;
+
+/// Fixed EVM precompile address that bridges `STATICCALL`/`CALL` into the
+/// tableland actor's `Query`/`Execute` methods, the same way the EVM's
+/// built-in 0x01-0x09 range gives bytecode a well-known address to target
+/// without knowing the callee's real on-chain address.
+pub const TABLELAND_PRECOMPILE_ADDR: u64 = 0xfe;
+
+/// Whether the surrounding EVM frame is a `STATICCALL`. Mirrors the
+/// static-vs-mutating distinction the interpreter already tracks for calls
+/// between EVM contracts, so the precompile can reject `Execute` the same
+/// way a plain `SSTORE` would revert under `STATICCALL`.
+#[derive(Debug, Clone, Copy)]
+pub struct PrecompileContext {
+    pub is_static: bool,
+}
+
+#[derive(Debug)]
+pub enum PrecompileError {
+    /// calldata could not be ABI-decoded into a statement (+ params)
+    InvalidInput,
+    /// a mutating statement was attempted from a `STATICCALL` frame
+    StaticCallViolation,
+    /// the send to the tableland actor failed or returned undecodable data
+    CallFailed,
+}
+
+/// ABI-decodes a SQL string and optional bound params from `input` and sends
+/// it to the tableland actor as `Query` (under `STATICCALL`) or `Execute`
+/// (under `CALL`), ABI-encoding the result back. A statement that isn't a
+/// plain `SELECT` is rejected under `STATICCALL` before any send is made,
+/// the same way a plain `SSTORE` reverts inside a static frame rather than
+/// silently becoming a no-op read.
+pub fn call(
+    rt: &impl Runtime,
+    input: &[u8],
+    ctx: PrecompileContext,
+) -> Result<Vec<u8>, PrecompileError> {
+    let (stmt, params) = decode_call(input)?;
+    let target = Address::new_id(TABLELAND_ACTOR_ID);
+
+    if ctx.is_static {
+        if is_write_statement(&stmt) {
+            return Err(PrecompileError::StaticCallViolation);
+        }
+        let params = QueryParams { stmt, params, limit: None, cursor: None };
+        let ret: QueryReturn = rt
+            .send(
+                &target,
+                TablelandMethod::Query as u64,
+                IpldBlock::serialize_cbor(&params).map_err(|_| PrecompileError::InvalidInput)?,
+                TokenAmount::zero(),
+            )
+            .map_err(|_| PrecompileError::CallFailed)?
+            .deserialize()
+            .map_err(|_| PrecompileError::CallFailed)?;
+        Ok(encode_query_return(&ret))
+    } else {
+        let params = ExecuteParams { stmts: vec![(stmt, params)] };
+        let ret: ExecuteReturn = rt
+            .send(
+                &target,
+                TablelandMethod::Execute as u64,
+                IpldBlock::serialize_cbor(&params).map_err(|_| PrecompileError::InvalidInput)?,
+                TokenAmount::zero(),
+            )
+            .map_err(|_| PrecompileError::CallFailed)?
+            .deserialize()
+            .map_err(|_| PrecompileError::CallFailed)?;
+        Ok(encode_uint256(ret.effected_rows as u64))
+    }
+}
+
+/// Whether `stmt` is anything other than a read (`SELECT ...`). Like the
+/// head/tail ABI (de|en)coding around it, this is a coarse lexical check
+/// rather than a real SQL parse -- it only needs to gate `STATICCALL`, and
+/// the actor's own `Execute`/`Query` dispatch is the actual authority on
+/// what a statement does.
+fn is_write_statement(stmt: &str) -> bool {
+    !stmt.trim_start().get(..6).is_some_and(|kw| kw.eq_ignore_ascii_case("select"))
+}
+
+/// Decodes `(string stmt, bytes32[] params)`-shaped calldata: a head of two
+/// offset words followed by the dynamic `string` and `bytes32[]` tails at
+/// those offsets, the same head/tail layout ABI-encodes any call with
+/// dynamic arguments. Each `bytes32` element is passed through to SQLite
+/// as a `Blob` bound parameter; a caller that wants a `Text`/`Integer`
+/// parameter instead encodes it into that word itself (e.g. left-padded
+/// UTF-8, or a big-endian integer) and the statement casts it back on the
+/// SQL side.
+fn decode_call(input: &[u8]) -> Result<(String, Vec<Value>), PrecompileError> {
+    let stmt_off = word_to_usize(read_word(input, 0)?)?;
+    let params_off = word_to_usize(read_word(input, 32)?)?;
+
+    let stmt_bytes = read_dynamic_bytes(input, stmt_off)?;
+    let stmt = String::from_utf8(stmt_bytes).map_err(|_| PrecompileError::InvalidInput)?;
+
+    let params_len = word_to_usize(read_word(input, params_off)?)?;
+    let mut params = Vec::with_capacity(params_len);
+    for i in 0..params_len {
+        let word = read_word(input, params_off + 32 + i * 32)?;
+        params.push(Value::Blob(word.to_vec()));
+    }
+
+    Ok((stmt, params))
+}
+
+/// Reads the 32-byte ABI word at `offset`.
+fn read_word(input: &[u8], offset: usize) -> Result<[u8; 32], PrecompileError> {
+    let slice = input.get(offset..offset + 32).ok_or(PrecompileError::InvalidInput)?;
+    let mut word = [0u8; 32];
+    word.copy_from_slice(slice);
+    Ok(word)
+}
+
+/// Widens an ABI word holding a length or offset into a `usize`, rejecting
+/// anything whose high 28 bytes aren't zero -- such a word can't be a valid
+/// index into calldata of realistic size, and letting it through would wrap
+/// on the `u32` truncation below.
+fn word_to_usize(word: [u8; 32]) -> Result<usize, PrecompileError> {
+    if word[..28].iter().any(|b| *b != 0) {
+        return Err(PrecompileError::InvalidInput);
+    }
+    Ok(u32::from_be_bytes(word[28..].try_into().unwrap()) as usize)
+}
+
+/// Reads a dynamic `bytes`/`string` ABI value (length word followed by its
+/// data) located at `offset`.
+fn read_dynamic_bytes(input: &[u8], offset: usize) -> Result<Vec<u8>, PrecompileError> {
+    let len = word_to_usize(read_word(input, offset)?)?;
+    input.get(offset + 32..offset + 32 + len).map(<[u8]>::to_vec).ok_or(PrecompileError::InvalidInput)
+}
+
+/// Encodes `QueryReturn`'s rows as ABI return data: one head word per value
+/// in row-major order (`Integer`/`Real`/`Null` inline, `Text`/`Blob` an
+/// offset into the tail), followed by the tail holding the dynamic values'
+/// length-prefixed data -- the same head/tail layout `decode_call` reads on
+/// the way in.
+fn encode_query_return(ret: &QueryReturn) -> Vec<u8> {
+    let values: Vec<&Value> = ret.rows.iter().flatten().collect();
+    let head_len = values.len() * 32;
+
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+
+    for value in values {
+        match value {
+            Value::Integer(i) => head.extend_from_slice(&encode_int256(*i)),
+            Value::Real(f) => {
+                // `f64` bits are carried through verbatim rather than going
+                // through `as u64` (which truncates the fraction and any
+                // negative value to zero), so the original value round-trips.
+                let mut word = [0u8; 32];
+                word[24..].copy_from_slice(&f.to_bits().to_be_bytes());
+                head.extend_from_slice(&word);
+            }
+            Value::Null => head.extend_from_slice(&[0u8; 32]),
+            Value::Text(s) => {
+                head.extend_from_slice(&encode_uint256((head_len + tail.len()) as u64));
+                tail.extend_from_slice(&encode_dynamic_bytes(s.as_bytes()));
+            }
+            Value::Blob(b) => {
+                head.extend_from_slice(&encode_uint256((head_len + tail.len()) as u64));
+                tail.extend_from_slice(&encode_dynamic_bytes(b));
+            }
+        }
+    }
+
+    head.extend_from_slice(&tail);
+    head
+}
+
+/// Encodes `data` as an ABI dynamic value's tail: a length word followed by
+/// the data, right-padded with zeros to a 32-byte boundary.
+fn encode_dynamic_bytes(data: &[u8]) -> Vec<u8> {
+    let mut out = encode_uint256(data.len() as u64);
+    out.extend_from_slice(data);
+    out.resize(out.len() + (32 - data.len() % 32) % 32, 0);
+    out
+}
+
+fn encode_uint256(v: u64) -> Vec<u8> {
+    let mut word = vec![0u8; 32];
+    word[24..].copy_from_slice(&v.to_be_bytes());
+    word
+}
+
+/// Encodes `v` as a two's-complement `int256` word: negative values sign-
+/// extend with `0xff` into the high 24 bytes instead of `encode_uint256`'s
+/// zero padding, so e.g. `-1` decodes as `int256(-1)` on the Solidity side
+/// rather than `2**64 - 1`.
+fn encode_int256(v: i64) -> Vec<u8> {
+    let fill = if v < 0 { 0xff } else { 0x00 };
+    let mut word = vec![fill; 32];
+    word[24..].copy_from_slice(&v.to_be_bytes());
+    word
+}
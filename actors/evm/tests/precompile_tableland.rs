@@ -0,0 +1,124 @@
+use fil_actor_evm::precompiles::{call_tableland, PrecompileContext, PrecompileError};
+use fil_actor_tableland_interface as tableland;
+use fil_actors_runtime::test_utils::*;
+use fvm_ipld_encoding::ipld_block::IpldBlock;
+use fvm_ipld_encoding::RawBytes;
+use fvm_shared::address::Address as FILAddress;
+use fvm_shared::econ::TokenAmount;
+use fvm_shared::error::ExitCode;
+use rusqlite::types::Value;
+
+/// ABI-encodes `(string stmt, bytes32[] params)` the way `decode_call` in
+/// `precompiles::tableland` expects it: two head offset words, then the
+/// `stmt` tail (length + UTF-8 bytes, padded to 32 bytes) and the `params`
+/// tail (length + one word per element).
+fn encode_call(stmt: &str, params: &[[u8; 32]]) -> Vec<u8> {
+    let stmt_bytes = stmt.as_bytes();
+    let stmt_padded_len = (stmt_bytes.len() + 31) / 32 * 32;
+
+    let stmt_off = 64u64;
+    let params_off = stmt_off + 32 + stmt_padded_len as u64;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&encode_uint256(stmt_off));
+    out.extend_from_slice(&encode_uint256(params_off));
+
+    out.extend_from_slice(&encode_uint256(stmt_bytes.len() as u64));
+    out.extend_from_slice(stmt_bytes);
+    out.resize(out.len() + (stmt_padded_len - stmt_bytes.len()), 0);
+
+    out.extend_from_slice(&encode_uint256(params.len() as u64));
+    for p in params {
+        out.extend_from_slice(p);
+    }
+
+    out
+}
+
+fn encode_uint256(v: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&v.to_be_bytes());
+    word
+}
+
+// NOTE: this snapshot doesn't carry the EVM interpreter or its precompile
+// dispatch table (see the NOTE in `precompiles/mod.rs`), so there's no
+// opcode-level `STATICCALL`/`CALL` to drive through an actual EVM contract
+// here. These tests call `call_tableland` directly instead -- the exact
+// function, and the exact `(rt, input, ctx)` arguments, the documented
+// dispatch arm would pass it -- rather than asserting a `Send` through a
+// fabricated target address no code actually resolves.
+
+fn fixed_target() -> FILAddress {
+    FILAddress::new_id(tableland::TABLELAND_ACTOR_ID)
+}
+
+#[test]
+fn test_static_query_sends_to_the_fixed_actor_id() {
+    let rt = MockRuntime::default();
+
+    let stmt = "SELECT id FROM t WHERE id = 1";
+    let query_params =
+        tableland::QueryParams { stmt: stmt.to_string(), params: vec![], limit: None, cursor: None };
+    let query_return = tableland::QueryReturn {
+        cols: vec!["id".to_string()],
+        rows: vec![vec![Value::Integer(1)]],
+        next_cursor: None,
+    };
+
+    rt.expect_send(
+        fixed_target(),
+        tableland::Method::Query as u64,
+        IpldBlock::serialize_cbor(&query_params).unwrap(),
+        TokenAmount::zero(),
+        RawBytes::serialize(&query_return).unwrap(),
+        ExitCode::OK,
+    );
+
+    let result =
+        call_tableland(&rt, &encode_call(stmt, &[]), PrecompileContext { is_static: true }).unwrap();
+
+    // A single `Integer(1)` row/column ABI-encodes as exactly one head word.
+    assert_eq!(&result[..], &encode_uint256(1)[..]);
+    rt.verify();
+}
+
+#[test]
+fn test_call_sends_execute_to_the_fixed_actor_id() {
+    let rt = MockRuntime::default();
+
+    let stmt = "INSERT INTO t (id) VALUES (2)";
+    let execute_params =
+        tableland::ExecuteParams { stmts: vec![(stmt.to_string(), vec![])] };
+    let execute_return = tableland::ExecuteReturn { effected_rows: 1 };
+
+    rt.expect_send(
+        fixed_target(),
+        tableland::Method::Execute as u64,
+        IpldBlock::serialize_cbor(&execute_params).unwrap(),
+        TokenAmount::zero(),
+        RawBytes::serialize(&execute_return).unwrap(),
+        ExitCode::OK,
+    );
+
+    let result =
+        call_tableland(&rt, &encode_call(stmt, &[]), PrecompileContext { is_static: false }).unwrap();
+
+    assert_eq!(&result[..], &encode_uint256(1)[..]);
+    rt.verify();
+}
+
+#[test]
+fn test_static_call_rejects_a_write() {
+    // No `expect_send` is registered -- if a mutating statement under
+    // `STATICCALL` ever reached a `Send` again, the mock runtime itself
+    // would panic on the unexpected call.
+    let rt = MockRuntime::default();
+
+    let stmt = "INSERT INTO t (id) VALUES (2)";
+    let err = call_tableland(&rt, &encode_call(stmt, &[]), PrecompileContext { is_static: true })
+        .unwrap_err();
+
+    assert!(matches!(err, PrecompileError::StaticCallViolation));
+    rt.verify();
+}
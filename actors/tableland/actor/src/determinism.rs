@@ -0,0 +1,113 @@
+use fil_actor_tableland_interface::Error;
+use fil_actors_runtime::runtime::Runtime;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::Connection;
+
+/// SQLite functions whose result is not a pure function of their arguments
+/// and would therefore fork state between validators if left unchecked.
+const DENYLISTED_FUNCTIONS: &[&str] =
+    &["RANDOM", "RANDOMBLOB", "DATETIME", "DATE", "TIME", "JULIANDAY", "STRFTIME"];
+
+/// `CURRENT_TIMESTAMP`/`CURRENT_TIME`/`CURRENT_DATE` are SQL keywords rather
+/// than callable functions, so they can't be intercepted with
+/// `create_scalar_function` and need their own handling.
+const DENYLISTED_KEYWORDS: &[&str] = &["CURRENT_TIMESTAMP", "CURRENT_TIME", "CURRENT_DATE"];
+
+/// A maximal run of identifier characters in `stmt`, with its position and
+/// whether it's immediately followed by `(` (ignoring whitespace) — i.e.
+/// whether it's used as a function call rather than, say, a column name.
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+    is_call: bool,
+}
+
+/// Splits `stmt` into identifier tokens. Used instead of a substring scan so
+/// that `DATE`/`TIME` only match the whole-word function names they denylist
+/// — not identifiers that merely contain them, like `last_update` or the
+/// `UPDATE` keyword.
+fn tokenize(stmt: &str) -> Vec<Token<'_>> {
+    let bytes = stmt.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_alphabetic() || bytes[i] == b'_' {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let mut j = i;
+            while j < bytes.len() && (bytes[j] as char).is_whitespace() {
+                j += 1;
+            }
+            tokens.push(Token {
+                text: &stmt[start..i],
+                start,
+                end: i,
+                is_call: j < bytes.len() && bytes[j] == b'(',
+            });
+        } else {
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// Rejects `stmt` in `Enforced` mode if it calls a denylisted
+/// non-deterministic function or references a denylisted keyword.
+pub fn reject_nondeterministic(stmt: &str) -> Result<(), Error> {
+    for token in tokenize(stmt) {
+        let upper = token.text.to_ascii_uppercase();
+        if token.is_call && DENYLISTED_FUNCTIONS.contains(&upper.as_str()) {
+            return Err(Error::NonDeterministic(upper));
+        }
+        if DENYLISTED_KEYWORDS.contains(&upper.as_str()) {
+            return Err(Error::NonDeterministic(upper));
+        }
+    }
+    Ok(())
+}
+
+/// Substitutes `CURRENT_TIMESTAMP`/`CURRENT_TIME`/`CURRENT_DATE` keyword
+/// references in `stmt` with a deterministic literal derived from `epoch`,
+/// since (unlike `RANDOM`/`DATETIME`/...) they're keywords SQLite evaluates
+/// directly and `create_scalar_function` cannot intercept.
+pub fn rewrite_relaxed_keywords(stmt: &str, epoch: i64) -> String {
+    let literal = format!("'{epoch}'");
+    let mut out = String::with_capacity(stmt.len());
+    let mut cursor = 0;
+    for token in tokenize(stmt) {
+        let upper = token.text.to_ascii_uppercase();
+        if DENYLISTED_KEYWORDS.contains(&upper.as_str()) {
+            out.push_str(&stmt[cursor..token.start]);
+            out.push_str(&literal);
+            cursor = token.end;
+        }
+    }
+    out.push_str(&stmt[cursor..]);
+    out
+}
+
+/// Registers deterministic overrides for every denylisted non-deterministic
+/// *function* on `conn`, seeded from the current block epoch so every
+/// validator computes the same substitute regardless of wall-clock time or
+/// local entropy. Used in `Relaxed` mode, alongside `rewrite_relaxed_keywords`
+/// for the denylisted keywords, in place of `reject_nondeterministic`.
+pub fn install_relaxed_overrides(conn: &Connection, rt: &impl Runtime) -> Result<(), Error> {
+    let epoch = rt.curr_epoch();
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+
+    conn.create_scalar_function("RANDOM", 0, flags, move |_| Ok(epoch))?;
+    conn.create_scalar_function("RANDOMBLOB", 1, flags, move |ctx| {
+        let n: usize = ctx.get(0)?;
+        Ok(epoch.to_le_bytes().iter().cycle().take(n).copied().collect::<Vec<u8>>())
+    })?;
+    conn.create_scalar_function("DATETIME", -1, flags, move |_| Ok(epoch.to_string()))?;
+    conn.create_scalar_function("DATE", -1, flags, move |_| Ok(epoch.to_string()))?;
+    conn.create_scalar_function("TIME", -1, flags, move |_| Ok(epoch.to_string()))?;
+    conn.create_scalar_function("JULIANDAY", -1, flags, move |_| Ok(epoch as f64))?;
+    conn.create_scalar_function("STRFTIME", -1, flags, move |_| Ok(epoch.to_string()))?;
+
+    Ok(())
+}
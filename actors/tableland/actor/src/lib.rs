@@ -0,0 +1,152 @@
+mod determinism;
+
+use fil_actor_tableland_interface::{
+    ConstructorParams, Determinism, ExecuteParams, ExecuteReturn, Method, QueryParams,
+    QueryReturn, SimulateParams, SimulateReturn, State,
+};
+use fil_actors_runtime::runtime::{ActorCode, Runtime};
+use fil_actors_runtime::{actor_dispatch, actor_error, ActorError};
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+fil_actors_runtime::wasm_trampoline!(Actor);
+
+pub struct Actor;
+
+/// Applies `determinism` to a batch of statements before they reach SQLite:
+/// `Enforced` rejects any denylisted call outright, `Relaxed` registers the
+/// deterministic function overrides on `conn` and rewrites denylisted
+/// keyword references (which overrides can't intercept) to a literal seeded
+/// from the current epoch.
+fn apply_determinism<R: Runtime>(
+    conn: &Connection,
+    rt: &R,
+    determinism: Determinism,
+    stmts: Vec<(String, Vec<Value>)>,
+) -> Result<Vec<(String, Vec<Value>)>, ActorError> {
+    match determinism {
+        Determinism::Enforced => {
+            for (stmt, _) in &stmts {
+                determinism::reject_nondeterministic(stmt)?;
+            }
+            Ok(stmts)
+        }
+        Determinism::Relaxed => {
+            determinism::install_relaxed_overrides(conn, rt)?;
+            let epoch = rt.curr_epoch();
+            Ok(stmts
+                .into_iter()
+                .map(|(stmt, params)| {
+                    (determinism::rewrite_relaxed_keywords(&stmt, epoch), params)
+                })
+                .collect())
+        }
+    }
+}
+
+/// Single-statement counterpart of `apply_determinism`, for `Query`.
+fn apply_determinism_one<R: Runtime>(
+    conn: &Connection,
+    rt: &R,
+    determinism: Determinism,
+    stmt: String,
+) -> Result<String, ActorError> {
+    match determinism {
+        Determinism::Enforced => {
+            determinism::reject_nondeterministic(&stmt)?;
+            Ok(stmt)
+        }
+        Determinism::Relaxed => {
+            determinism::install_relaxed_overrides(conn, rt)?;
+            Ok(determinism::rewrite_relaxed_keywords(&stmt, rt.curr_epoch()))
+        }
+    }
+}
+
+impl Actor {
+    pub fn constructor(rt: &impl Runtime, params: ConstructorParams) -> Result<(), ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+        let state =
+            State::new(rt.store().clone(), params.db, params.buck_size, params.determinism)
+                .map_err(|e| actor_error!(illegal_argument; "failed to store db: {}", e))?;
+        rt.create(&state)?;
+        Ok(())
+    }
+
+    /// Mutates the stored database by running each statement with its bound
+    /// params, then flushes only the touched buckets back to `State`.
+    /// Statements are checked (or made deterministic) according to the mode
+    /// fixed at construction so every validator applies the same mutation.
+    pub fn execute(rt: &impl Runtime, params: ExecuteParams) -> Result<ExecuteReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        let effected_rows = rt.transaction(move |st: &mut State, rt| {
+            let mut db = st.load_db(rt.store().clone())?;
+            let stmts = apply_determinism(&db.conn, rt, st.determinism, params.stmts)?;
+            let effected_rows = db.execute(&stmts)?;
+            st.save_db(&mut db)?;
+            Ok(effected_rows)
+        })?;
+
+        Ok(ExecuteReturn { effected_rows })
+    }
+
+    /// Runs a read-only statement against the stored database and returns
+    /// its result set without mutating state. When `params.limit` is set the
+    /// result is paged deterministically: `QueryReturn::next_cursor` carries
+    /// the value to pass back as `params.cursor` to fetch the next page, and
+    /// is `None` once the result is exhausted.
+    pub fn query(rt: &impl Runtime, params: QueryParams) -> Result<QueryReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        let st: State = rt.state()?;
+        let db = st
+            .load_db(rt.store().clone())
+            .map_err(|e| actor_error!(illegal_state; "failed to load db: {}", e))?;
+
+        let stmt = apply_determinism_one(&db.conn, rt, st.determinism, params.stmt)?;
+
+        let result = if params.limit.is_none() && params.cursor.is_none() {
+            db.query(&stmt, &params.params)
+        } else {
+            db.query_page(&stmt, &params.params, params.limit, params.cursor)
+        };
+        result.map_err(|e| actor_error!(illegal_argument; "query failed: {}", e))
+    }
+
+    /// Dry-runs a batch of statements inside a transaction that is always
+    /// rolled back, returning the would-be affected-row count and result
+    /// sets without flushing any bucket back to `State`. Lets a client probe
+    /// the effect and shape of a statement before paying to land it.
+    pub fn simulate(rt: &impl Runtime, params: SimulateParams) -> Result<SimulateReturn, ActorError> {
+        rt.validate_immediate_caller_accept_any()?;
+
+        let st: State = rt.state()?;
+        let db = st
+            .load_db(rt.store().clone())
+            .map_err(|e| actor_error!(illegal_state; "failed to load db: {}", e))?;
+
+        let stmts = apply_determinism(&db.conn, rt, st.determinism, params.stmts)?;
+
+        let (effected_rows, results) = db
+            .simulate(&stmts)
+            .map_err(|e| actor_error!(illegal_argument; "simulate failed: {}", e))?;
+
+        Ok(SimulateReturn { effected_rows, results })
+    }
+}
+
+impl ActorCode for Actor {
+    type Methods = Method;
+
+    fn name() -> &'static str {
+        "Tableland"
+    }
+
+    actor_dispatch! {
+        Constructor => constructor,
+        Execute => execute,
+        Query => query,
+        Simulate => simulate,
+    }
+}
@@ -0,0 +1,101 @@
+use cid::Cid;
+use fvm_ipld_amt::Amt;
+use fvm_ipld_blockstore::Blockstore;
+
+use crate::errors::Error;
+use crate::types::SQLITE_PAGE_SIZE;
+
+/// Size, in bytes, of a single bucket: `buck_size` SQLite pages.
+pub fn bucket_bytes(buck_size: usize) -> usize {
+    buck_size * SQLITE_PAGE_SIZE
+}
+
+/// Bucket-indexed storage over an IPLD AMT: bucket `i` is leaf `i` of the
+/// AMT, so flushing after a write that only touched bucket `i` only re-puts
+/// that leaf and the handful of AMT nodes on the path to it, rather than
+/// rewriting the whole database blob as a single block.
+pub struct BucketStore<BS: Blockstore + Clone> {
+    amt: Amt<Vec<u8>, BS>,
+    buck_size: usize,
+    /// Logical length of the database in bytes. Tracked here (rather than
+    /// separately in `DB`/`State`) because it's the VFS file's own notion of
+    /// size -- `TablelandFile::file_size`/`truncate` read and update it
+    /// directly, the same way a real file's size lives with the file, not
+    /// with whatever opened it.
+    len: usize,
+}
+
+impl<BS: Blockstore + Clone> BucketStore<BS> {
+    pub fn new(store: BS, buck_size: usize, len: usize) -> Self {
+        Self { amt: Amt::new(store), buck_size, len }
+    }
+
+    pub fn load(store: BS, root: &Cid, buck_size: usize, len: usize) -> Result<Self, Error> {
+        let amt = Amt::load(root, store).map_err(|e| Error::InvalidStatement(e.to_string()))?;
+        Ok(Self { amt, buck_size, len })
+    }
+
+    /// Splits `db` into fixed-size buckets and writes each as an AMT leaf.
+    /// Used once, at construction, to seed the bucket map from the blob a
+    /// client supplies up front.
+    pub fn seed(store: BS, db: &[u8], buck_size: usize) -> Result<Self, Error> {
+        let mut buckets = Self::new(store, buck_size, db.len());
+        for (i, chunk) in db.chunks(bucket_bytes(buck_size)).enumerate() {
+            buckets.put_bucket(i as u64, chunk.to_vec())?;
+        }
+        Ok(buckets)
+    }
+
+    pub fn buck_size(&self) -> usize {
+        self.buck_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+
+    /// Reads bucket `idx`, zero-filled if it has never been written.
+    pub fn get_bucket(&self, idx: u64) -> Result<Vec<u8>, Error> {
+        match self.amt.get(idx).map_err(|e| Error::InvalidStatement(e.to_string()))? {
+            Some(bucket) => Ok(bucket.clone()),
+            None => Ok(vec![0u8; bucket_bytes(self.buck_size)]),
+        }
+    }
+
+    /// Writes bucket `idx`, padding short buckets up to the fixed bucket
+    /// size so every leaf in the AMT is the same length.
+    pub fn put_bucket(&mut self, idx: u64, mut bytes: Vec<u8>) -> Result<(), Error> {
+        bytes.resize(bucket_bytes(self.buck_size), 0);
+        self.amt.set(idx, bytes).map_err(|e| Error::InvalidStatement(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Flushes dirty buckets and returns the new AMT root to store in
+    /// `State`.
+    pub fn flush(&mut self) -> Result<Cid, Error> {
+        self.amt.flush().map_err(|e| Error::InvalidStatement(e.to_string()))
+    }
+
+    /// Reassembles the full database blob by walking every bucket in order.
+    /// The VFS read/write path does not call this; it goes through
+    /// `get_bucket`/`put_bucket` directly so a single
+    /// page touch stays O(bucket size), not O(database size).
+    pub fn dump(&self) -> Result<Vec<u8>, Error> {
+        let bucket_len = bucket_bytes(self.buck_size);
+        let bucket_count = (self.len + bucket_len - 1) / bucket_len;
+        let mut out = Vec::with_capacity(bucket_count * bucket_len);
+        for i in 0..bucket_count {
+            out.extend_from_slice(&self.get_bucket(i as u64)?);
+        }
+        out.truncate(self.len);
+        Ok(out)
+    }
+}
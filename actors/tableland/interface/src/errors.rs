@@ -0,0 +1,29 @@
+use fil_actors_runtime::ActorError;
+use fvm_shared::error::ExitCode;
+use thiserror::Error;
+
+/// Errors specific to the tableland actor. These are translated into an
+/// `ActorError` with `ExitCode::USR_ILLEGAL_ARGUMENT` at the call boundary so
+/// callers see a conventional FVM exit code while still being able to match
+/// on the underlying cause when debugging off-chain.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("sql error: {0}")]
+    Sql(#[from] rusqlite::Error),
+    #[error("statement is not deterministic: {0}")]
+    NonDeterministic(String),
+    #[error("invalid statement: {0}")]
+    InvalidStatement(String),
+}
+
+impl From<Error> for ActorError {
+    fn from(err: Error) -> Self {
+        ActorError::illegal_argument(err.to_string())
+    }
+}
+
+impl From<Error> for (ExitCode, String) {
+    fn from(err: Error) -> Self {
+        (ExitCode::USR_ILLEGAL_ARGUMENT, err.to_string())
+    }
+}
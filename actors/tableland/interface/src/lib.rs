@@ -1,10 +1,12 @@
+mod bucket_store;
 mod errors;
 mod state;
 mod types;
+mod vfs;
 
 pub use errors::Error;
 pub use state::{State, DB};
 pub use types::{
-    ConstructorParams, ExecuteParams, ExecuteReturn, Method, QueryParams, QueryReturn,
-    SQLITE_PAGE_SIZE,
+    ConstructorParams, Determinism, ExecuteParams, ExecuteReturn, Method, QueryParams,
+    QueryReturn, SimulateParams, SimulateReturn, SQLITE_PAGE_SIZE, TABLELAND_ACTOR_ID,
 };
\ No newline at end of file
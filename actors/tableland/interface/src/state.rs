@@ -0,0 +1,265 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use cid::Cid;
+use fvm_ipld_blockstore::Blockstore;
+use fvm_ipld_encoding::tuple::*;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OpenFlags};
+
+use crate::bucket_store::BucketStore;
+use crate::errors::Error;
+use crate::types::{Determinism, QueryReturn};
+use crate::vfs::{self, VFS_NAME};
+
+/// On-chain state for the tableland actor: the AMT root of the database's
+/// page buckets, the total database length in bytes (so buckets can be
+/// reassembled or truncated correctly), the bucket size the database was
+/// constructed with, and the determinism mode fixed at construction time.
+#[derive(Debug, Clone, Serialize_tuple, Deserialize_tuple)]
+pub struct State {
+    pub buckets: Cid,
+    pub len: usize,
+    pub buck_size: usize,
+    pub determinism: Determinism,
+}
+
+impl State {
+    pub fn new<BS: Blockstore + Clone + 'static>(
+        store: BS,
+        db: Vec<u8>,
+        buck_size: usize,
+        determinism: Determinism,
+    ) -> Result<Self, Error> {
+        let len = db.len();
+        let mut buckets = BucketStore::seed(store, &db, buck_size)?;
+        let buckets = buckets.flush()?;
+        Ok(Self { buckets, len, buck_size, determinism })
+    }
+
+    /// Loads the bucket map and opens a live SQLite connection backed by it
+    /// through the `tableland` VFS, so reads/writes only touch the buckets
+    /// a statement actually needs rather than the whole database.
+    pub fn load_db<BS: Blockstore + Clone + 'static>(&self, store: BS) -> Result<DB<BS>, Error> {
+        let buckets = BucketStore::load(store, &self.buckets, self.buck_size, self.len)?;
+        DB::open(buckets)
+    }
+
+    /// Flushes the buckets touched by `db` and updates the stored root.
+    pub fn save_db<BS: Blockstore + Clone + 'static>(
+        &mut self,
+        db: &mut DB<BS>,
+    ) -> Result<(), Error> {
+        let (buckets, len) = db.flush()?;
+        self.buckets = buckets;
+        self.len = len;
+        Ok(())
+    }
+}
+
+/// A live SQLite connection over a page-bucketed database. Callers load a
+/// `DB` from `State`, mutate it through `execute`/`query`/`simulate`, then
+/// `flush` it and update `State` with the returned bucket root -- only the
+/// buckets whose pages changed are ever re-put to the blockstore.
+pub struct DB<BS: Blockstore + Clone + 'static> {
+    pub conn: Connection,
+    buckets: Rc<RefCell<BucketStore<BS>>>,
+}
+
+impl<BS: Blockstore + Clone + 'static> DB<BS> {
+    fn open(buckets: BucketStore<BS>) -> Result<Self, Error> {
+        let buckets = Rc::new(RefCell::new(buckets));
+
+        // `sqlite_vfs::register` errors if `VFS_NAME` is already registered,
+        // which it will be from the second `load_db` in a process onward.
+        // The registered `TablelandVfs` doesn't capture `buckets` itself --
+        // it reads whichever store `set_current_buckets` last installed --
+        // so reusing the existing registration here is correct, not stale.
+        vfs::set_current_buckets(buckets.clone());
+        vfs::ensure_registered::<BS>();
+
+        let conn = Connection::open_with_flags_and_vfs(
+            "tableland.db",
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE,
+            VFS_NAME,
+        )?;
+        // `execute()` runs single statements with no `BEGIN`, and `simulate`
+        // wraps its own `BEGIN`/`ROLLBACK`; either way SQLite still wants a
+        // rollback journal for the write. `TablelandVfs::open` only serves
+        // the main db file, so the journal is kept off the VFS entirely by
+        // holding it in memory instead.
+        conn.pragma_update(None, "journal_mode", "MEMORY")?;
+
+        Ok(Self { conn, buckets })
+    }
+
+    /// Flushes dirty buckets and returns the new bucket root and total
+    /// database length, for `State::save_db` to persist.
+    pub fn flush(&mut self) -> Result<(Cid, usize), Error> {
+        let mut buckets = self.buckets.borrow_mut();
+        let root = buckets.flush()?;
+        Ok((root, buckets.len()))
+    }
+
+    /// Executes each statement with its bound params in order, returning the
+    /// total number of affected rows.
+    pub fn execute(&self, stmts: &[(String, Vec<Value>)]) -> Result<usize, Error> {
+        let mut effected_rows = 0usize;
+        for (stmt, params) in stmts {
+            effected_rows += self.conn.execute(stmt, params_from_iter(params.iter()))?;
+        }
+        Ok(effected_rows)
+    }
+
+    /// Runs a single read-only statement with its bound params and
+    /// materializes the result set.
+    pub fn query(&self, stmt: &str, params: &[Value]) -> Result<QueryReturn, Error> {
+        let mut prepared = self.conn.prepare(stmt)?;
+        let cols: Vec<String> =
+            prepared.column_names().into_iter().map(str::to_string).collect();
+
+        let mut rows = Vec::new();
+        let mut result_rows = prepared.query(params_from_iter(params.iter()))?;
+        while let Some(row) = result_rows.next()? {
+            let mut values = Vec::with_capacity(cols.len());
+            for i in 0..cols.len() {
+                values.push(row.get::<_, Value>(i)?);
+            }
+            rows.push(values);
+        }
+
+        Ok(QueryReturn { cols, rows, next_cursor: None })
+    }
+
+    /// Runs a single read-only statement like `query`, but pages through the
+    /// result deterministically: `stmt` must end in an `ORDER BY <col>
+    /// [ASC|DESC]` clause, which fixes the page order. The cursor itself is
+    /// not `order_col`'s value but a row sequence number assigned over that
+    /// same order (`ROW_NUMBER() OVER (ORDER BY <col> [ASC|DESC])`), so ties
+    /// in `order_col` at a page boundary still resolve to a unique cutoff --
+    /// unlike bounding on `order_col` directly, which would skip or repeat
+    /// rows sharing the boundary value. The same cursor always resumes at
+    /// the same position regardless of concurrent reads, since the ordering
+    /// and the cutoff are both pinned to `stmt` itself.
+    pub fn query_page(
+        &self,
+        stmt: &str,
+        params: &[Value],
+        limit: Option<u64>,
+        cursor: Option<Value>,
+    ) -> Result<QueryReturn, Error> {
+        let (order_col, desc) = order_by_column(stmt).ok_or_else(|| {
+            Error::InvalidStatement(
+                "paged queries must end in a deterministic ORDER BY <col> [ASC|DESC]".to_string(),
+            )
+        })?;
+        let dir = if desc { "DESC" } else { "ASC" };
+
+        let mut wrapped = format!(
+            "SELECT * FROM (SELECT *, ROW_NUMBER() OVER (ORDER BY {order_col} {dir}) AS __tableland_seq FROM ({stmt}))"
+        );
+        let mut bound = params.to_vec();
+        if let Some(cursor) = cursor {
+            wrapped.push_str(" WHERE __tableland_seq > ?");
+            bound.push(cursor);
+        }
+        // `ROW_NUMBER()` already fixes each row's position for the whole
+        // result set, so re-ordering by it (rather than by `order_col`
+        // again) is what keeps ties in `order_col` in the same relative
+        // order across pages.
+        wrapped.push_str(" ORDER BY __tableland_seq ASC");
+        if let Some(limit) = limit {
+            wrapped.push_str(&format!(" LIMIT {limit}"));
+        }
+
+        let mut result = self.query(&wrapped, &bound)?;
+
+        let seq_idx = result.cols.iter().position(|c| c == "__tableland_seq");
+        result.next_cursor = match (limit, seq_idx) {
+            (Some(limit), Some(i)) if result.rows.len() as u64 == limit => {
+                result.rows.last().map(|row| row[i].clone())
+            }
+            _ => None,
+        };
+
+        // `__tableland_seq` is paging bookkeeping, not part of `stmt`'s own
+        // result set -- strip it back out before handing the page back.
+        if let Some(i) = seq_idx {
+            result.cols.remove(i);
+            for row in &mut result.rows {
+                row.remove(i);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Runs `stmts` inside a transaction that is always rolled back,
+    /// collecting affected-row counts for mutating statements and result
+    /// sets for statements that return rows, without persisting any change
+    /// to the connection.
+    pub fn simulate(
+        &self,
+        stmts: &[(String, Vec<Value>)],
+    ) -> Result<(usize, Vec<QueryReturn>), Error> {
+        self.conn.execute_batch("BEGIN")?;
+
+        let outcome = (|| -> Result<(usize, Vec<QueryReturn>), Error> {
+            let mut effected_rows = 0usize;
+            let mut results = Vec::new();
+            for (stmt, params) in stmts {
+                if self.conn.prepare(stmt)?.column_count() > 0 {
+                    results.push(self.query(stmt, params)?);
+                } else {
+                    effected_rows += self.conn.execute(stmt, params_from_iter(params.iter()))?;
+                }
+            }
+            Ok((effected_rows, results))
+        })();
+
+        self.conn.execute_batch("ROLLBACK")?;
+        outcome
+    }
+}
+
+/// Extracts the column and direction from a trailing `ORDER BY <col>
+/// [ASC|DESC]` clause. This is a coarse scan rather than a full SQL parse:
+/// it only needs to find the clause `query_page` itself appends the
+/// `WHERE`/`ORDER BY`/`LIMIT` wrapper around, so anything it can't pin down
+/// unambiguously -- a second sort key, an ordinal (`ORDER BY 1`), or a
+/// qualified column (`t.id`) -- is rejected outright (via the `None` case)
+/// rather than paged against a guessed-at ordering.
+fn order_by_column(stmt: &str) -> Option<(String, bool)> {
+    let upper = stmt.to_ascii_uppercase();
+    let idx = upper.rfind("ORDER BY")?;
+    let rest = stmt[idx + "ORDER BY".len()..].trim();
+
+    // Only a single sort key is supported: a second `, col2` would need its
+    // own cursor column, which `QueryParams::cursor` doesn't carry.
+    let mut keys = rest.split(',');
+    let key = keys.next()?.trim();
+    if keys.next().is_some() {
+        return None;
+    }
+    let mut tokens = key.split_whitespace();
+    let col = tokens.next()?;
+
+    let desc = match tokens.next() {
+        None => false,
+        Some(dir) if dir.eq_ignore_ascii_case("ASC") => false,
+        Some(dir) if dir.eq_ignore_ascii_case("DESC") => true,
+        Some(_) => return None,
+    };
+    if tokens.next().is_some() {
+        return None;
+    }
+
+    let is_plain_column = !col.is_empty()
+        && col.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && col.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_plain_column {
+        return None;
+    }
+
+    Some((col.to_string(), desc))
+}
@@ -1,19 +1,42 @@
 use fvm_ipld_encoding::strict_bytes;
 use fvm_ipld_encoding::tuple::*;
-use fvm_shared::METHOD_CONSTRUCTOR;
+use fvm_shared::{ActorID, METHOD_CONSTRUCTOR};
 use num_derive::FromPrimitive;
 use rusqlite::types::Value;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_with::{serde_as, DeserializeAs, SerializeAs};
 
 pub const SQLITE_PAGE_SIZE: usize = 4096;
 
+/// Fixed `ActorID` the tableland actor is deployed at, the same way the
+/// other builtin singletons (reward, cron, power, the EAM, ...) are always
+/// reachable at a reserved low `ActorID` rather than one a caller looks up.
+/// Callers that only know an EVM-side handle to the actor (the precompile
+/// bridge, chiefly) resolve `Address::new_id(TABLELAND_ACTOR_ID)` rather than
+/// taking a target address as input.
+pub const TABLELAND_ACTOR_ID: ActorID = 103;
+
 #[derive(FromPrimitive)]
 #[repr(u64)]
 pub enum Method {
     Constructor = METHOD_CONSTRUCTOR,
     Execute = 2,
     Query = 3,
+    Simulate = 4,
+}
+
+/// Selects how the actor handles SQLite's non-deterministic builtins
+/// (`RANDOM()`, `CURRENT_TIMESTAMP`, `datetime('now')`, `randomblob()`, ...).
+/// `Enforced` rejects any statement that references one outright; `Relaxed`
+/// instead overrides them with deterministic substitutes seeded from
+/// runtime-available values. The mode is fixed at construction and carried
+/// in `State` so it cannot drift between calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(i64)]
+pub enum Determinism {
+    Enforced = 0,
+    Relaxed = 1,
 }
 
 #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
@@ -21,12 +44,20 @@ pub struct ConstructorParams {
     #[serde(with = "strict_bytes")]
     pub db: Vec<u8>,
     pub buck_size: usize,
+    pub determinism: Determinism,
 }
 
+/// A single statement paired with its positional bound values, mirroring the
+/// split between opaque call data and typed params used for message
+/// parameters: the statement text stays static across calls while the
+/// `ValueDef`s carry the per-call data, so identical statements always hash
+/// to identical CBOR regardless of how callers format literals.
+#[serde_as]
 #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
 #[serde(transparent)]
 pub struct ExecuteParams {
-    pub stmts: Vec<String>,
+    #[serde_as(as = "Vec<(String, Vec<ValueDef>)>")]
+    pub stmts: Vec<(String, Vec<Value>)>,
 }
 
 #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
@@ -35,10 +66,39 @@ pub struct ExecuteReturn {
     pub effected_rows: usize,
 }
 
+#[serde_as]
 #[derive(Debug, Serialize_tuple, Deserialize_tuple)]
-#[serde(transparent)]
 pub struct QueryParams {
     pub stmt: String,
+    #[serde_as(as = "Vec<ValueDef>")]
+    pub params: Vec<Value>,
+    /// Maximum number of rows to return. `stmt` must carry a deterministic
+    /// `ORDER BY` so that, combined with `cursor`, the same cursor always
+    /// resumes at the same position regardless of concurrent reads.
+    pub limit: Option<u64>,
+    /// Resumes a prior paged `Query` after the row this cursor identifies.
+    /// This is a row sequence number over `stmt`'s `ORDER BY`, not that
+    /// column's own value, so it still pins a unique position even when the
+    /// `ORDER BY` column has duplicate values at a page boundary.
+    #[serde_as(as = "Option<ValueDef>")]
+    pub cursor: Option<Value>,
+}
+
+/// Runs `stmts` the same way `Execute` would, but always rolls the
+/// transaction back: a dry run a client can use to probe the effect and
+/// shape of a batch before paying to land it on chain.
+#[serde_as]
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+#[serde(transparent)]
+pub struct SimulateParams {
+    #[serde_as(as = "Vec<(String, Vec<ValueDef>)>")]
+    pub stmts: Vec<(String, Vec<Value>)>,
+}
+
+#[derive(Debug, Serialize_tuple, Deserialize_tuple)]
+pub struct SimulateReturn {
+    pub effected_rows: usize,
+    pub results: Vec<QueryReturn>,
 }
 
 #[serde_as]
@@ -47,6 +107,10 @@ pub struct QueryReturn {
     pub cols: Vec<String>,
     #[serde_as(as = "Vec<Vec<ValueDef>>")]
     pub rows: Vec<Vec<Value>>,
+    /// `Some(cursor)` to resume the same `Query` and fetch the next page,
+    /// `None` once the result is exhausted. Pass it back as `QueryParams::cursor`.
+    #[serde_as(as = "Option<ValueDef>")]
+    pub next_cursor: Option<Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -0,0 +1,200 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fvm_ipld_blockstore::Blockstore;
+use sqlite_vfs::{LockKind, OpenKind, OpenOptions};
+
+use crate::bucket_store::{bucket_bytes, BucketStore};
+
+/// Name this VFS is registered under. `DB::open` passes it as the VFS
+/// argument of `Connection::open_with_flags_and_vfs` so every SQLite page
+/// read/write goes through `BucketStore` instead of the OS filesystem.
+pub const VFS_NAME: &str = "tableland";
+
+thread_local! {
+    /// The bucket store the next `TablelandVfs::open` call should hand out.
+    /// `sqlite_vfs` only lets a VFS be registered under a given name once
+    /// per process, but `State::load_db` opens a fresh `BucketStore` on
+    /// every call -- so rather than capturing one store at registration
+    /// time (which would go stale the moment a second `load_db` ran), the
+    /// registered `TablelandVfs` is stateless and reads whichever store
+    /// `set_current_buckets` most recently installed.
+    static CURRENT_BUCKETS: RefCell<Option<Rc<dyn Any>>> = RefCell::new(None);
+}
+
+/// Installs `buckets` as the store the next VFS `open()` call will use.
+/// Must be called before `Connection::open_with_flags_and_vfs` each time a
+/// `DB` is opened.
+pub fn set_current_buckets<BS: Blockstore + Clone + 'static>(
+    buckets: Rc<RefCell<BucketStore<BS>>>,
+) {
+    CURRENT_BUCKETS.with(|cell| *cell.borrow_mut() = Some(buckets));
+}
+
+fn current_buckets<BS: Blockstore + Clone + 'static>() -> Rc<RefCell<BucketStore<BS>>> {
+    CURRENT_BUCKETS.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .expect("tableland VFS opened before a database was installed")
+            .clone()
+            .downcast::<RefCell<BucketStore<BS>>>()
+            .expect("tableland VFS backing store type changed between opens")
+    })
+}
+
+/// Registers the `tableland` VFS if it isn't already. Safe to call on every
+/// `DB::open`: a repeat registration errors in `sqlite_vfs`, but since the
+/// registered `TablelandVfs` reads `CURRENT_BUCKETS` fresh on every `open()`
+/// rather than holding state from registration time, reusing the
+/// already-registered instance is correct and the error is tolerated rather
+/// than treated as fatal.
+pub fn ensure_registered<BS: Blockstore + Clone + 'static>() {
+    let _ = sqlite_vfs::register(VFS_NAME, TablelandVfs::<BS>::default(), false);
+}
+
+/// A `sqlite_vfs::Vfs` that hands out file handles over whatever
+/// `BucketStore` `CURRENT_BUCKETS` currently holds. Only the main database
+/// file is ever opened through it -- no journal/wal/temp files, since `DB`
+/// sets `journal_mode = MEMORY` so SQLite never asks the VFS for one.
+pub struct TablelandVfs<BS: Blockstore + Clone + 'static> {
+    _marker: std::marker::PhantomData<BS>,
+}
+
+impl<BS: Blockstore + Clone + 'static> Default for TablelandVfs<BS> {
+    fn default() -> Self {
+        Self { _marker: std::marker::PhantomData }
+    }
+}
+
+impl<BS: Blockstore + Clone + 'static> sqlite_vfs::Vfs for TablelandVfs<BS> {
+    type Handle = TablelandFile<BS>;
+
+    fn open(&self, _db: &str, opts: OpenOptions) -> Result<Self::Handle, std::io::Error> {
+        if opts.kind != OpenKind::MainDb {
+            return Err(std::io::Error::from(std::io::ErrorKind::Unsupported));
+        }
+        Ok(TablelandFile { buckets: current_buckets::<BS>() })
+    }
+
+    fn delete(&self, _db: &str) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+
+    fn exists(&self, _db: &str) -> Result<bool, std::io::Error> {
+        Ok(true)
+    }
+
+    fn temporary_name(&self) -> String {
+        VFS_NAME.to_string()
+    }
+
+    fn random(&self, buffer: &mut [i8]) {
+        // Zeros rather than real entropy, so anything SQLite draws from this
+        // VFS's randomness source (e.g. rowid collision probing, whose picks
+        // end up in stored rows) is identical on every validator.
+        buffer.fill(0);
+    }
+
+    fn sleep(&self, duration: Duration) -> Duration {
+        duration
+    }
+}
+
+/// A `sqlite_vfs::File` handle whose reads/writes are translated into
+/// `get_bucket`/`put_bucket` calls: a write only touches the buckets whose
+/// byte range the write actually overlaps, so a single-page mutation stays
+/// O(bucket size) instead of O(database size).
+pub struct TablelandFile<BS: Blockstore + Clone + 'static> {
+    buckets: Rc<RefCell<BucketStore<BS>>>,
+}
+
+impl<BS: Blockstore + Clone + 'static> sqlite_vfs::File for TablelandFile<BS> {
+    fn file_size(&self) -> Result<u64, std::io::Error> {
+        Ok(self.buckets.borrow().len() as u64)
+    }
+
+    fn truncate(&mut self, size: u64) -> Result<(), std::io::Error> {
+        self.buckets.borrow_mut().set_len(size as usize);
+        Ok(())
+    }
+
+    fn write(&mut self, pos: u64, buf: &[u8]) -> Result<usize, std::io::Error> {
+        let mut buckets = self.buckets.borrow_mut();
+        let bucket_len = bucket_bytes(buckets.buck_size());
+        let mut written = 0;
+        while written < buf.len() {
+            let abs = pos + written as u64;
+            let idx = abs / bucket_len as u64;
+            let offset = (abs % bucket_len as u64) as usize;
+            let n = (bucket_len - offset).min(buf.len() - written);
+
+            let mut bucket = buckets
+                .get_bucket(idx)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+            bucket[offset..offset + n].copy_from_slice(&buf[written..written + n]);
+            buckets
+                .put_bucket(idx, bucket)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+            written += n;
+        }
+
+        let end = pos + written as u64;
+        if end > buckets.len() as u64 {
+            buckets.set_len(end as usize);
+        }
+
+        Ok(written)
+    }
+
+    fn read(&mut self, pos: u64, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        let buckets = self.buckets.borrow();
+        let bucket_len = bucket_bytes(buckets.buck_size());
+        let mut read = 0;
+        while read < buf.len() {
+            let abs = pos + read as u64;
+            let idx = abs / bucket_len as u64;
+            let offset = (abs % bucket_len as u64) as usize;
+            let n = (bucket_len - offset).min(buf.len() - read);
+
+            let bucket = buckets
+                .get_bucket(idx)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+            buf[read..read + n].copy_from_slice(&bucket[offset..offset + n]);
+
+            read += n;
+        }
+        Ok(read)
+    }
+
+    fn sync(&mut self, _data_only: bool) -> Result<(), std::io::Error> {
+        // Buckets are only flushed to the blockstore (and `State` updated)
+        // once per `Execute` call, at the actor layer, not on every fsync
+        // the SQLite pager issues; `Query`/`Simulate` never flush them.
+        Ok(())
+    }
+
+    fn lock(&mut self, _lock: LockKind) -> Result<bool, std::io::Error> {
+        // A single actor invocation never has concurrent writers, so every
+        // lock request trivially succeeds.
+        Ok(true)
+    }
+
+    fn unlock(&mut self, _lock: LockKind) -> Result<bool, std::io::Error> {
+        Ok(true)
+    }
+
+    fn reserved(&mut self) -> Result<bool, std::io::Error> {
+        Ok(false)
+    }
+
+    fn current_lock(&self) -> Result<LockKind, std::io::Error> {
+        Ok(LockKind::None)
+    }
+
+    fn set_chunk_size(&mut self, _chunk_size: usize) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+}